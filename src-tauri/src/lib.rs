@@ -1,19 +1,182 @@
-use std::process::{Child, Command as StdCommand, Stdio};
-use std::sync::Mutex;
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader};
+use std::process::Command as StdCommand;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
 
 use log::{error, info};
-use tauri::{Emitter, Manager};
+use serde::Deserialize;
+use shared_child::SharedChild;
+use tauri::{AppHandle, Emitter, Manager};
+
+/// Body returned by the backend's `/health` endpoint, e.g.
+/// `{ "status": "ok", "db": "ready" }`.
+#[derive(Deserialize)]
+struct HealthPayload {
+    status: String,
+}
+
+/// User-overridable backend launch profile, loaded from `backend.json` in
+/// the app config dir if present. Lets users point at a different Python
+/// env, flip `--reload`, or inject secrets without rebuilding the app.
+/// Any field left unset falls back to the built-in dev/release defaults.
+#[derive(Deserialize, Default)]
+#[serde(default)]
+struct BackendConfig {
+    /// Overrides the dev `uv` invocation / release PyInstaller binary path.
+    executable: Option<String>,
+    /// Extra arguments appended after the built-in `--host`/`--port` args.
+    args: Vec<String>,
+    /// Overrides whether `--reload` is passed (defaults to dev-mode only).
+    reload: Option<bool>,
+    /// Environment variables merged onto the child process.
+    env: HashMap<String, String>,
+}
+
+impl BackendConfig {
+    /// Load `<app-config-dir>/backend.json`, falling back to defaults if the
+    /// file is missing or fails to parse.
+    fn load(app_handle: &AppHandle) -> Self {
+        let Ok(config_dir) = app_handle.path().app_config_dir() else {
+            return Self::default();
+        };
+        let config_path = config_dir.join("backend.json");
+
+        let contents = match std::fs::read_to_string(&config_path) {
+            Ok(contents) => contents,
+            Err(_) => return Self::default(),
+        };
+
+        match serde_json::from_str(&contents) {
+            Ok(config) => {
+                info!("Loaded backend config from {:?}", config_path);
+                config
+            }
+            Err(e) => {
+                error!("Failed to parse {:?}: {}", config_path, e);
+                Self::default()
+            }
+        }
+    }
+}
+
+/// Backoff schedule for backend restarts: 500ms, 1s, 2s, 4s, 8s, 16s, capped at 30s.
+const RESTART_BACKOFFS_MS: &[u64] = &[500, 1_000, 2_000, 4_000, 8_000, 16_000, 30_000];
+
+/// Give up restarting the backend after this many consecutive restart
+/// attempts (failed respawns included) without it becoming healthy.
+const MAX_RESTART_ATTEMPTS: usize = RESTART_BACKOFFS_MS.len();
+
+#[cfg(windows)]
+mod windows_job {
+    use std::io;
+
+    use windows_sys::Win32::Foundation::CloseHandle;
+    use windows_sys::Win32::System::JobObjects::{
+        AssignProcessToJobObject, CreateJobObjectW, JobObjectExtendedLimitInformation,
+        SetInformationJobObject, JOBOBJECT_EXTENDED_LIMIT_INFORMATION,
+        JOB_OBJECT_LIMIT_KILL_ON_JOB_CLOSE,
+    };
+    use windows_sys::Win32::System::Threading::{OpenProcess, PROCESS_ALL_ACCESS};
+
+    /// A Win32 Job Object that the backend process is assigned to.
+    ///
+    /// The job is configured with `JOB_OBJECT_LIMIT_KILL_ON_JOB_CLOSE`, so
+    /// closing this handle terminates the backend and any grandchildren it
+    /// spawned (e.g. `uvicorn` workers) atomically.
+    pub struct JobHandle(isize);
+
+    // The underlying HANDLE is only ever touched through CloseHandle, which
+    // is safe to call from any thread.
+    unsafe impl Send for JobHandle {}
+    unsafe impl Sync for JobHandle {}
+
+    impl JobHandle {
+        /// Create a kill-on-close job object and assign the already-running
+        /// process `pid` to it.
+        ///
+        /// This is assigned *after* the process has been created via
+        /// `std::process::Command`/`SharedChild`, not at creation time with
+        /// `CREATE_SUSPENDED` — `std::process::Command` gives no hook to
+        /// assign a job before the process starts running. That leaves a
+        /// narrow race: any grandchild the backend forks before this call
+        /// completes (e.g. a PyInstaller bootloader re-exec'ing immediately)
+        /// won't be in the job and will survive the job's kill-on-close.
+        /// Closing this gap for real needs a raw `CreateProcessW` call with
+        /// `CREATE_SUSPENDED` plus assign-before-resume, which is out of
+        /// scope here; this is the best effort possible on top of
+        /// `std::process::Command`.
+        pub fn assign(pid: u32) -> io::Result<Self> {
+            unsafe {
+                let job = CreateJobObjectW(std::ptr::null(), std::ptr::null());
+                if job == 0 {
+                    return Err(io::Error::last_os_error());
+                }
+
+                let mut info: JOBOBJECT_EXTENDED_LIMIT_INFORMATION = std::mem::zeroed();
+                info.BasicLimitInformation.LimitFlags = JOB_OBJECT_LIMIT_KILL_ON_JOB_CLOSE;
+                let configured = SetInformationJobObject(
+                    job,
+                    JobObjectExtendedLimitInformation,
+                    &info as *const _ as *const _,
+                    std::mem::size_of::<JOBOBJECT_EXTENDED_LIMIT_INFORMATION>() as u32,
+                );
+                if configured == 0 {
+                    let err = io::Error::last_os_error();
+                    CloseHandle(job);
+                    return Err(err);
+                }
+
+                let process = OpenProcess(PROCESS_ALL_ACCESS, 0, pid);
+                if process == 0 {
+                    let err = io::Error::last_os_error();
+                    CloseHandle(job);
+                    return Err(err);
+                }
+
+                let assigned = AssignProcessToJobObject(job, process);
+                CloseHandle(process);
+                if assigned == 0 {
+                    let err = io::Error::last_os_error();
+                    CloseHandle(job);
+                    return Err(err);
+                }
+
+                Ok(JobHandle(job))
+            }
+        }
+    }
+
+    impl Drop for JobHandle {
+        fn drop(&mut self) {
+            unsafe {
+                CloseHandle(self.0);
+            }
+        }
+    }
+}
 
 /// Holds the backend child process for lifecycle management.
 struct BackendState {
-    child: Mutex<Option<Child>>,
+    child: Mutex<Option<Arc<SharedChild>>>,
+    /// Set by the exit handler before killing the backend, so the supervisor
+    /// task can tell a deliberate shutdown apart from an unexpected crash.
+    shutting_down: AtomicBool,
+    #[cfg(windows)]
+    job: Mutex<Option<windows_job::JobHandle>>,
 }
 
-/// Kill a process and its entire process group (handles `uv run` -> `uvicorn` child).
-fn kill_process_tree(child: &mut Child) {
+/// Kill a process and its entire process tree.
+///
+/// On Unix this escalates SIGTERM -> grace period -> SIGKILL against the
+/// whole process group. On Windows the process was assigned to a
+/// kill-on-close Job Object right after it was spawned, so a plain `kill()`
+/// here plus dropping that job handle takes down `uvicorn` and any
+/// grandchildren in the job (see `windows_job::JobHandle::assign` for
+/// grandchildren started before the assignment, which are not in it).
+fn kill_process_tree(child: &SharedChild) {
     let pid = child.id() as i32;
 
-    // Try killing the process group first (negative PID = process group)
     #[cfg(unix)]
     {
         unsafe {
@@ -35,23 +198,72 @@ fn kill_process_tree(child: &mut Child) {
     let _ = child.wait();
 }
 
-/// Poll the backend port until it accepts TCP connections or timeout.
-async fn wait_for_backend(timeout_secs: u64) -> bool {
+/// Poll the backend's `/health` endpoint until it reports a healthy status,
+/// or until `timeout_secs` elapses.
+///
+/// A bare TCP connect succeeds as soon as the socket is listening, even
+/// while FastAPI is still importing models and `/health` would 503. This
+/// instead does a real HTTP GET and only considers the backend healthy once
+/// the JSON body's `status` field says so, so a reachable-but-unhealthy
+/// backend (e.g. DB not ready) isn't mistaken for a booting one. Emits
+/// `backend-starting` with the last-seen status after every poll so the UI
+/// can distinguish "still booting" from "waiting for database".
+async fn wait_for_backend(app_handle: &AppHandle, port: u16, timeout_secs: u64) -> bool {
+    let url = format!("http://127.0.0.1:{}/health", port);
+    let client = reqwest::Client::new();
     let deadline = std::time::Instant::now() + std::time::Duration::from_secs(timeout_secs);
-    while std::time::Instant::now() < deadline {
-        if tokio::net::TcpStream::connect("127.0.0.1:8008")
-            .await
-            .is_ok()
-        {
+
+    while let Some(remaining) = deadline.checked_duration_since(std::time::Instant::now()) {
+        // A hung request (e.g. the socket accepts but the backend never
+        // responds) must not be able to block past `deadline` on its own, so
+        // bound each poll to whatever time is left.
+        let status = match tokio::time::timeout(remaining, client.get(&url).send()).await {
+            Ok(Ok(response)) => match response.json::<HealthPayload>().await {
+                Ok(payload) => payload.status,
+                Err(e) => {
+                    error!("Backend /health returned an unparseable body: {}", e);
+                    "unhealthy".to_string()
+                }
+            },
+            Ok(Err(e)) if e.is_connect() => "starting".to_string(),
+            Ok(Err(e)) => {
+                error!("Backend /health request failed: {}", e);
+                "unreachable".to_string()
+            }
+            Err(_) => {
+                error!("Backend /health request timed out");
+                "unreachable".to_string()
+            }
+        };
+
+        let healthy = status == "ok";
+        let _ = app_handle.emit("backend-starting", serde_json::json!({ "status": status }));
+        if healthy {
             return true;
         }
+
         tokio::time::sleep(std::time::Duration::from_millis(500)).await;
     }
     false
 }
 
-/// Spawn a command in its own process group so we can kill the whole tree.
-fn spawn_in_process_group(cmd: &mut StdCommand) -> std::io::Result<Child> {
+/// Bind an OS-assigned free port and immediately release it, so the backend
+/// can be started on a port that's actually free. This leaves a (tiny, racy
+/// but standard) window between picking the port and the backend binding it,
+/// which is the same tradeoff every "find a free port" helper makes.
+fn pick_free_port() -> u16 {
+    let listener =
+        std::net::TcpListener::bind("127.0.0.1:0").expect("Failed to bind an ephemeral port");
+    listener
+        .local_addr()
+        .expect("Failed to read ephemeral port")
+        .port()
+}
+
+/// Spawn a command in its own process group (Unix) so we can kill the whole
+/// tree later, returning a `SharedChild` so the supervisor task and the
+/// exit handler can observe/kill the same child safely from different tasks.
+fn spawn_in_process_group(cmd: &mut StdCommand) -> std::io::Result<SharedChild> {
     #[cfg(unix)]
     {
         use std::os::unix::process::CommandExt;
@@ -63,7 +275,257 @@ fn spawn_in_process_group(cmd: &mut StdCommand) -> std::io::Result<Child> {
             });
         }
     }
-    cmd.spawn()
+    SharedChild::spawn(cmd)
+}
+
+/// Forward each line from a backend stdout/stderr pipe to the webview as a
+/// `backend-log` event, tagged with the stream name and a monotonic sequence
+/// number so the UI can render an ordered console.
+///
+/// `seq` is shared (via `Arc<AtomicU64>`) between the stdout and stderr
+/// forwarders for one backend process, so the sequence is globally
+/// monotonic across both streams rather than each counting independently —
+/// otherwise a frontend sorting by `seq` couldn't tell whether a stdout line
+/// happened before or after a stderr line with the same number.
+///
+/// Reads block on the pipe, so this runs on the blocking thread pool; the
+/// loop exits (and the task ends) once the pipe's write end closes, which
+/// happens as soon as the backend process is killed.
+fn spawn_log_forwarder(
+    app_handle: AppHandle,
+    reader: os_pipe::PipeReader,
+    stream: &'static str,
+    seq: Arc<AtomicU64>,
+) {
+    tauri::async_runtime::spawn_blocking(move || {
+        let mut reader = BufReader::new(reader);
+        let mut line = String::new();
+        loop {
+            line.clear();
+            match reader.read_line(&mut line) {
+                Ok(0) => break,
+                Ok(_) => {
+                    let text = line.trim_end_matches(['\n', '\r']);
+                    let _ = app_handle.emit(
+                        "backend-log",
+                        serde_json::json!({
+                            "stream": stream,
+                            "seq": seq.fetch_add(1, Ordering::Relaxed),
+                            "line": text,
+                        }),
+                    );
+                }
+                Err(e) => {
+                    error!("Error reading backend {} stream: {}", stream, e);
+                    break;
+                }
+            }
+        }
+        info!("Backend {} log forwarder exiting", stream);
+    });
+}
+
+/// Spawn the backend process on a freshly-picked free port, wiring up its
+/// process group, Windows Job Object, and stdout/stderr log forwarders. Used
+/// both for the initial launch and for supervised restarts after a crash.
+///
+/// The command itself comes from, in order: the `executable`/`args`/`reload`
+/// overrides in [`BackendConfig`], or else the built-in dev (`uv run
+/// uvicorn`) / release (PyInstaller binary) default. `BackendConfig::env` is
+/// merged onto the child's environment either way. Returns `Err` instead of
+/// panicking when a *configured* executable doesn't exist, since that's a
+/// user mistake we can report rather than a broken install.
+fn spawn_backend(app_handle: &AppHandle) -> Result<(Arc<SharedChild>, u16), String> {
+    let port = pick_free_port();
+    let config = BackendConfig::load(app_handle);
+    // `--reload` only makes sense for the built-in dev `uvicorn` invocation;
+    // a configured executable is assumed to be a standalone binary unless
+    // the user explicitly asks for `--reload`.
+    let reload = config
+        .reload
+        .unwrap_or(config.executable.is_none() && cfg!(debug_assertions));
+
+    // Pipes for forwarding backend stdout/stderr to the frontend. We use
+    // `os_pipe` rather than `Stdio::piped()` so the reader ends stay in our
+    // hands no matter how the child is wrapped.
+    let (stdout_reader, stdout_writer) =
+        os_pipe::pipe().map_err(|e| format!("Failed to create stdout pipe: {}", e))?;
+    let (stderr_reader, stderr_writer) =
+        os_pipe::pipe().map_err(|e| format!("Failed to create stderr pipe: {}", e))?;
+
+    let mut cmd = if let Some(executable) = &config.executable {
+        // CONFIG: user-specified backend executable
+        let backend_bin = std::path::PathBuf::from(executable);
+        if !backend_bin.is_file() {
+            return Err(format!(
+                "Configured backend executable not found: {:?}",
+                backend_bin
+            ));
+        }
+
+        info!("CONFIG: spawning backend from {:?} on port {}", backend_bin, port);
+
+        let mut cmd = StdCommand::new(&backend_bin);
+        cmd.args(["--host", "127.0.0.1", "--port", &port.to_string()]);
+        if let Some(backend_dir) = backend_bin.parent() {
+            cmd.current_dir(backend_dir);
+        }
+        cmd
+    } else if cfg!(debug_assertions) {
+        // DEV: use uv from the source backend/ directory
+        let backend_dir = std::path::PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .parent()
+            .expect("CARGO_MANIFEST_DIR has no parent")
+            .join("backend");
+
+        info!("DEV: spawning backend from {:?} on port {}", backend_dir, port);
+
+        let mut cmd = StdCommand::new("uv");
+        cmd.args([
+            "run",
+            "uvicorn",
+            "main:app",
+            "--host",
+            "127.0.0.1",
+            "--port",
+            &port.to_string(),
+        ])
+        .current_dir(&backend_dir);
+        cmd
+    } else {
+        // RELEASE: run PyInstaller binary from resources
+        let resource_dir = app_handle
+            .path()
+            .resource_dir()
+            .expect("Failed to resolve resource dir");
+        let backend_dir = resource_dir.join("backend");
+        let backend_bin = backend_dir.join("mnemosyne-backend");
+
+        info!("RELEASE: spawning backend from {:?} on port {}", backend_bin, port);
+
+        let mut cmd = StdCommand::new(&backend_bin);
+        cmd.args(["--host", "127.0.0.1", "--port", &port.to_string()])
+            .current_dir(&backend_dir);
+        cmd
+    };
+
+    if reload {
+        cmd.arg("--reload");
+    }
+    cmd.args(&config.args)
+        .envs(&config.env)
+        .stdout(stdout_writer)
+        .stderr(stderr_writer);
+
+    let child = spawn_in_process_group(&mut cmd)
+        .map_err(|e| format!("Failed to spawn backend: {}", e))?;
+
+    info!("Backend spawned with PID: {}", child.id());
+
+    let log_seq = Arc::new(AtomicU64::new(0));
+    spawn_log_forwarder(app_handle.clone(), stdout_reader, "stdout", log_seq.clone());
+    spawn_log_forwarder(app_handle.clone(), stderr_reader, "stderr", log_seq);
+
+    #[cfg(windows)]
+    {
+        match windows_job::JobHandle::assign(child.id()) {
+            Ok(job) => {
+                *app_handle.state::<BackendState>().job.lock().unwrap() = Some(job);
+            }
+            Err(e) => error!("Failed to assign backend to job object: {}", e),
+        }
+    }
+
+    Ok((Arc::new(child), port))
+}
+
+/// Wait on the backend child and, if it exits unexpectedly (i.e. not because
+/// the exit handler is shutting it down), respawn it (on a freshly-picked
+/// port) with exponential backoff and re-run the health check. Emits
+/// `backend-restarted` after each successful respawn (healthy or not),
+/// `backend-spawn-failed` for each respawn that fails, or `backend-failed`
+/// once `MAX_RESTART_ATTEMPTS` consecutive restart attempts have been
+/// exhausted.
+async fn supervise_backend(app_handle: AppHandle, mut child: Arc<SharedChild>, mut port: u16) {
+    let mut attempt = 0usize;
+    loop {
+        let wait_result = {
+            let child = child.clone();
+            tauri::async_runtime::spawn_blocking(move || child.wait()).await
+        };
+
+        let state = app_handle.state::<BackendState>();
+        if state.shutting_down.load(Ordering::SeqCst) {
+            info!("Backend exited for shutdown, supervisor stopping.");
+            return;
+        }
+
+        match wait_result {
+            Ok(Ok(status)) => error!("Backend exited unexpectedly: {}", status),
+            Ok(Err(e)) => error!("Failed to wait on backend: {}", e),
+            Err(e) => {
+                error!("Backend wait task failed: {}", e);
+                return;
+            }
+        }
+
+        // Keep retrying the respawn itself (with the same backoff schedule)
+        // until it succeeds or we run out of attempts, so a transient spawn
+        // failure (e.g. a bad config path) doesn't make the next loop
+        // iteration re-observe the same already-exited `child`.
+        let (new_child, new_port) = loop {
+            if attempt >= MAX_RESTART_ATTEMPTS {
+                error!("Backend restart failed {} times in a row, giving up.", attempt);
+                let _ = app_handle.emit("backend-failed", ());
+                return;
+            }
+
+            let backoff_ms = RESTART_BACKOFFS_MS[attempt];
+            attempt += 1;
+            info!(
+                "Restarting backend in {}ms (attempt {}/{})",
+                backoff_ms, attempt, MAX_RESTART_ATTEMPTS
+            );
+            tokio::time::sleep(std::time::Duration::from_millis(backoff_ms)).await;
+
+            // The backoff sleep can run up to 30s; re-check here in case the
+            // app quit (and the exit handler already killed/forgot the old
+            // child) while we were asleep, so we don't spawn a detached
+            // backend the exit handler will never see.
+            if state.shutting_down.load(Ordering::SeqCst) {
+                info!("Shutdown requested during restart backoff, supervisor stopping.");
+                return;
+            }
+
+            match spawn_backend(&app_handle) {
+                Ok(spawned) => break spawned,
+                Err(e) => {
+                    error!("Failed to restart backend: {}", e);
+                    // Reuse `backend-spawn-failed` rather than overloading
+                    // `backend-restarted` with a second, incompatible shape
+                    // (`{healthy, port}` is the only payload listeners
+                    // should ever see on that event).
+                    let _ = app_handle
+                        .emit("backend-spawn-failed", serde_json::json!({ "error": e }));
+                }
+            }
+        };
+        child = new_child;
+        port = new_port;
+        *state.child.lock().unwrap() = Some(child.clone());
+
+        let healthy = wait_for_backend(&app_handle, port, 30).await;
+        if healthy {
+            info!("Backend restarted and healthy on port {}", port);
+            attempt = 0;
+        } else {
+            error!("Restarted backend did not become healthy within 30s");
+        }
+        let _ = app_handle.emit(
+            "backend-restarted",
+            serde_json::json!({ "healthy": healthy, "port": port }),
+        );
+    }
 }
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
@@ -73,6 +535,9 @@ pub fn run() {
         .plugin(tauri_plugin_dialog::init())
         .manage(BackendState {
             child: Mutex::new(None),
+            shutting_down: AtomicBool::new(false),
+            #[cfg(windows)]
+            job: Mutex::new(None),
         })
         .setup(|app| {
             if cfg!(debug_assertions) {
@@ -85,72 +550,40 @@ pub fn run() {
 
             let app_handle = app.handle().clone();
 
-            // Spawn the Python backend process
-            let child = if cfg!(debug_assertions) {
-                // DEV: use uv from the source backend/ directory
-                let backend_dir = std::path::PathBuf::from(env!("CARGO_MANIFEST_DIR"))
-                    .parent()
-                    .expect("CARGO_MANIFEST_DIR has no parent")
-                    .join("backend");
-
-                info!("DEV: spawning backend from {:?}", backend_dir);
-
-                let mut cmd = StdCommand::new("uv");
-                cmd.args([
-                    "run",
-                    "uvicorn",
-                    "main:app",
-                    "--host",
-                    "127.0.0.1",
-                    "--port",
-                    "8008",
-                    "--reload",
-                ])
-                .current_dir(&backend_dir)
-                .stdout(Stdio::piped())
-                .stderr(Stdio::piped());
-
-                spawn_in_process_group(&mut cmd)
-                    .expect("Failed to spawn backend via uv. Is uv installed?")
-            } else {
-                // RELEASE: run PyInstaller binary from resources
-                let resource_dir = app_handle
-                    .path()
-                    .resource_dir()
-                    .expect("Failed to resolve resource dir");
-                let backend_dir = resource_dir.join("backend");
-                let backend_bin = backend_dir.join("mnemosyne-backend");
-
-                info!("RELEASE: spawning backend from {:?}", backend_bin);
-
-                let mut cmd = StdCommand::new(&backend_bin);
-                cmd.args(["--host", "127.0.0.1", "--port", "8008"])
-                    .current_dir(&backend_dir)
-                    .stdout(Stdio::piped())
-                    .stderr(Stdio::piped());
-
-                spawn_in_process_group(&mut cmd).unwrap_or_else(|e| {
-                    panic!("Failed to spawn backend from {:?}: {}", backend_bin, e)
-                })
+            let (child, port) = match spawn_backend(&app_handle) {
+                Ok(spawned) => spawned,
+                Err(e) => {
+                    error!("Failed to spawn backend: {}", e);
+                    let _ = app_handle.emit(
+                        "backend-spawn-failed",
+                        serde_json::json!({ "error": e }),
+                    );
+                    return Ok(());
+                }
             };
-
-            info!("Backend spawned with PID: {}", child.id());
-
             let state = app.state::<BackendState>();
-            *state.child.lock().unwrap() = Some(child);
+            *state.child.lock().unwrap() = Some(child.clone());
 
             // Spawn async health check task
+            let health_app_handle = app_handle.clone();
             tauri::async_runtime::spawn(async move {
                 info!("Waiting for backend to become healthy...");
-                let healthy = wait_for_backend(30).await;
+                let healthy = wait_for_backend(&health_app_handle, port, 30).await;
                 if healthy {
-                    info!("Backend is healthy on port 8008");
+                    info!("Backend is healthy on port {}", port);
                 } else {
                     error!("Backend did not become healthy within 30s");
                 }
-                let _ = app_handle.emit("backend-ready", healthy);
+                let _ = health_app_handle.emit(
+                    "backend-ready",
+                    serde_json::json!({ "healthy": healthy, "port": port }),
+                );
             });
 
+            // Spawn supervisor task: restarts the backend with backoff if it
+            // crashes unexpectedly.
+            tauri::async_runtime::spawn(supervise_backend(app_handle, child, port));
+
             Ok(())
         })
         .build(tauri::generate_context!())
@@ -159,16 +592,17 @@ pub fn run() {
     app.run(|app_handle, event| {
         if let tauri::RunEvent::Exit = event {
             info!("App exiting, shutting down backend...");
-            let child = app_handle
-                .state::<BackendState>()
-                .child
-                .lock()
-                .unwrap()
-                .take();
-            if let Some(mut child) = child {
-                kill_process_tree(&mut child);
+            let state = app_handle.state::<BackendState>();
+            state.shutting_down.store(true, Ordering::SeqCst);
+            let child = state.child.lock().unwrap().take();
+            if let Some(child) = child {
+                kill_process_tree(&child);
                 info!("Backend process tree killed.");
             }
+            #[cfg(windows)]
+            {
+                app_handle.state::<BackendState>().job.lock().unwrap().take();
+            }
         }
     });
 }